@@ -3,20 +3,138 @@ use crate::query::model::Query;
 use crate::query::DataSource;
 use crate::query::Dimension;
 use crate::query::Granularity;
+use crate::transport::HttpTransport;
+use futures::stream::Stream;
+use futures::stream::StreamExt;
+#[cfg(feature = "reqwest-backend")]
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use thiserror::Error;
+use zookeeper::{WatchedEvent, Watcher, ZooKeeper};
 
+/// Envelope for a single `TopN` time bucket: `{ timestamp, result: [..] }`.
 #[derive(Deserialize, Serialize, Debug)]
-pub struct QueryResult<T: DeserializeOwned + std::fmt::Debug + Serialize> {
-    // timestamp: String,
+pub struct TopNResult<T: DeserializeOwned + std::fmt::Debug + Serialize> {
+    pub timestamp: String,
     #[serde(bound = "")]
-    result: Vec<T>,
+    pub result: Vec<T>,
+}
+
+/// Envelope for a single `Timeseries` bucket: `{ timestamp, result: {..} }`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TimeseriesResult<T: DeserializeOwned + std::fmt::Debug + Serialize> {
+    pub timestamp: String,
+    #[serde(bound = "")]
+    pub result: T,
+}
+
+/// Envelope for a single `GroupBy` row: `{ version, timestamp, event: {..} }`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GroupByResult<T: DeserializeOwned + std::fmt::Debug + Serialize> {
+    pub version: String,
+    pub timestamp: String,
+    #[serde(bound = "")]
+    pub event: T,
+}
+
+/// Envelope for a `Scan` segment: `{ segmentId, columns, events: [..] }`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ScanResult<T: DeserializeOwned + std::fmt::Debug + Serialize> {
+    #[serde(rename = "segmentId")]
+    pub segment_id: String,
+    pub columns: Vec<String>,
+    #[serde(bound = "")]
+    pub events: Vec<T>,
+}
+
+/// Correctly typed response for a native query, tagged by the query kind.
+/// [`DruidClient::query`] returns the `TopN`, `GroupBy` or `Scan` envelope
+/// matching the submitted `Query` variant; for `Search`, `TimeBoundary`,
+/// `SegmentMetadata` and `DataSourceMetadata` it returns
+/// `DruidClientError::UseTypedMethod` instead of a `QueryResponse`.
+#[derive(Debug)]
+pub enum QueryResponse<T: DeserializeOwned + std::fmt::Debug + Serialize> {
+    TopN(Vec<TopNResult<T>>),
+    Timeseries(Vec<TimeseriesResult<T>>),
+    GroupBy(Vec<GroupByResult<T>>),
+    Scan(Vec<ScanResult<T>>),
+}
+
+/// One hit of a `Search` query: the dimension it was found in, the matched
+/// value and the number of rows it occurred in.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SearchHit {
+    pub dimension: String,
+    pub value: String,
+    pub count: usize,
+}
+
+/// A `Search` result is grouped per time bucket.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SearchResult {
+    pub timestamp: String,
+    pub result: Vec<SearchHit>,
+}
+
+/// Envelope for a `TimeBoundary` query, reporting the min/max timestamps of
+/// the queried interval. Either bound may be absent when `bound` narrows the
+/// query to a single side.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TimeBoundaryResult {
+    pub timestamp: String,
+    pub result: TimeBoundary,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TimeBoundary {
+    #[serde(rename = "minTime")]
+    pub min_time: Option<String>,
+    #[serde(rename = "maxTime")]
+    pub max_time: Option<String>,
+}
+
+/// Envelope for a `SegmentMetadata` query: one entry per analyzed segment.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SegmentMetadataResult {
+    pub id: String,
+    pub intervals: Vec<String>,
+    pub columns: std::collections::HashMap<String, ColumnAnalysis>,
+    pub size: Option<usize>,
+    #[serde(rename = "numRows")]
+    pub num_rows: Option<usize>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ColumnAnalysis {
+    #[serde(rename = "type")]
+    pub column_type: String,
+    pub size: usize,
+    pub cardinality: Option<usize>,
+    #[serde(rename = "errorMessage")]
+    pub error_message: Option<String>,
+}
+
+/// Envelope for a `DataSourceMetadata` query, carrying the last ingested
+/// event time for the datasource.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataSourceMetadataResult {
+    pub timestamp: String,
+    pub result: DataSourceMetadata,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataSourceMetadata {
+    #[serde(rename = "maxIngestedEventTime")]
+    pub max_ingested_event_time: Option<String>,
 }
 
 #[derive(Error, Debug)]
 pub enum DruidClientError {
+    #[cfg(feature = "reqwest-backend")]
     #[error("http connection error")]
     HttpConnection { source: reqwest::Error },
     #[error("the data for key `{0}` is not available")]
@@ -27,32 +145,369 @@ pub enum DruidClientError {
     ParsingError { source: serde_json::Error },
     #[error("Server responded with an error")]
     ServerError { response: String },
+    #[error("query type `{query_type}` returns a custom envelope; call the dedicated typed method instead of `query`")]
+    UseTypedMethod { query_type: &'static str },
+    #[error("no brokers available to serve the request")]
+    NoBrokersAvailable,
+    #[error("all brokers failed after {attempts} attempts")]
+    AllBrokersFailed { attempts: usize },
+    #[error("transport error: {message}")]
+    Transport { message: String },
+    #[error("zookeeper error")]
+    Zookeeper { source: zookeeper::ZkError },
     #[error("unknown data store error")]
     Unknown,
 }
+
+impl DruidClientError {
+    /// Whether this error represents a connection-level failure worth retrying
+    /// against another broker, independent of the HTTP backend in use.
+    fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "reqwest-backend")]
+            DruidClientError::HttpConnection { .. } => true,
+            DruidClientError::Transport { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// Default Druid discovery path that brokers announce themselves under.
+const DEFAULT_BROKER_DISCOVERY_PATH: &str = "/druid/discovery/druid:broker";
+
+/// Sleeps using the timer of whichever runtime backend is enabled, so the
+/// retry backoff doesn't hard-wire the query layer to tokio.
+#[cfg(feature = "reqwest-backend")]
+async fn backend_sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(all(not(feature = "reqwest-backend"), feature = "async-std-backend"))]
+async fn backend_sleep(duration: Duration) {
+    async_std::task::sleep(duration).await;
+}
+
+#[cfg(not(any(feature = "reqwest-backend", feature = "async-std-backend")))]
+async fn backend_sleep(_duration: Duration) {}
+
+/// A broker announcement as published by Druid under the discovery znode. The
+/// blob is a `DiscoveryDruidNode` whose connection details live under the
+/// nested `druidNode` object; only the fields we need to build a base URL are
+/// decoded.
+#[derive(Deserialize, Debug)]
+struct BrokerAnnouncement {
+    #[serde(rename = "druidNode")]
+    druid_node: DruidNode,
+}
+
+#[derive(Deserialize, Debug)]
+struct DruidNode {
+    host: String,
+    #[serde(rename = "plaintextPort")]
+    plaintext_port: Option<i32>,
+    #[serde(rename = "tlsPort")]
+    tls_port: Option<i32>,
+    #[serde(rename = "enableTlsPort")]
+    enable_tls_port: Option<bool>,
+}
+
+impl BrokerAnnouncement {
+    fn base_url(&self) -> String {
+        let node = &self.druid_node;
+        match (node.enable_tls_port, node.tls_port) {
+            (Some(true), Some(tls_port)) => {
+                format!("https://{}:{}/druid/v2/", node.host, tls_port)
+            }
+            _ => format!(
+                "http://{}:{}/druid/v2/",
+                node.host,
+                node.plaintext_port.unwrap_or(8082)
+            ),
+        }
+    }
+}
+
+/// Controls how [`DruidClient`] reacts to connection-level failures: how many
+/// brokers to try, the per-request timeout applied around each send (backend
+/// agnostic), and how long to wait between attempts.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub timeout: Duration,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            timeout: Duration::from_secs(30),
+            backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Callback invoked with `(broker_url, request_body, response_body)` for every
+/// completed query, letting users capture raw payloads for logging or replay.
+pub type CaptureHook = Arc<dyn Fn(&str, &str, &str) + Send + Sync>;
+
 pub struct DruidClient {
-    http_client: Client,
+    transport: Arc<dyn HttpTransport>,
+    retry_policy: RetryPolicy,
+    capture: Option<CaptureHook>,
     nodes: Vec<String>,
+    /// Live pool of broker base URLs. With a static node list this is built
+    /// once; with ZooKeeper autodiscovery it is rebuilt on every watch event.
+    pool: Arc<RwLock<Vec<String>>>,
+    /// Round-robin cursor shared across requests.
+    cursor: Arc<AtomicUsize>,
+    /// Kept alive for the lifetime of the client so the ZK session (and its
+    /// watches) stays open; `None` for static node lists.
+    _zk: Option<Arc<ZooKeeper>>,
+}
+
+/// Watcher that rebuilds the broker pool whenever the children of the
+/// discovery znode change.
+struct BrokerWatcher {
+    zk: Arc<RwLock<Option<Arc<ZooKeeper>>>>,
+    path: String,
+    pool: Arc<RwLock<Vec<String>>>,
+}
+
+impl Watcher for BrokerWatcher {
+    fn handle(&self, _event: WatchedEvent) {
+        if let Some(zk) = self.zk.read().unwrap().clone() {
+            refresh_pool(&zk, &self.path, &self.pool, self);
+        }
+    }
+}
+
+impl Clone for BrokerWatcher {
+    fn clone(&self) -> Self {
+        BrokerWatcher {
+            zk: self.zk.clone(),
+            path: self.path.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// Reads the children of `path`, decodes each broker announcement blob and
+/// swaps the resulting base URLs into `pool`, re-arming the watch. With the
+/// `tracing` feature, decode and ZK errors are logged so an empty pool isn't
+/// invisible; without it they are dropped.
+fn refresh_pool(zk: &ZooKeeper, path: &str, pool: &Arc<RwLock<Vec<String>>>, watcher: &BrokerWatcher) {
+    let children = match zk.get_children_w(path, watcher.clone()) {
+        Ok(children) => children,
+        Err(err) => {
+            let _ = &err;
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%path, error = %err, "failed to list broker discovery children");
+            return;
+        }
+    };
+    let mut urls = Vec::with_capacity(children.len());
+    for child in children {
+        let child_path = format!("{}/{}", path, child);
+        match zk.get_data(&child_path, false) {
+            Ok((data, _stat)) => match serde_json::from_slice::<BrokerAnnouncement>(&data) {
+                Ok(announcement) => urls.push(announcement.base_url()),
+                Err(err) => {
+                    let _ = &err;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%child_path, error = %err, "failed to decode broker announcement");
+                }
+            },
+            Err(err) => {
+                let _ = &err;
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%child_path, error = %err, "failed to read broker announcement");
+            }
+        }
+    }
+    #[cfg(feature = "tracing")]
+    if urls.is_empty() {
+        tracing::warn!(%path, "broker discovery returned no usable brokers");
+    }
+    *pool.write().unwrap() = urls;
+}
+
+/// Incrementally carves complete top-level objects out of the streamed Scan
+/// response, which is a JSON array of segment envelopes. It tracks brace depth
+/// (ignoring braces inside strings) so a segment can be decoded the moment its
+/// closing `}` arrives, without waiting for the rest of the array.
+struct SegmentSplitter {
+    buf: String,
+    depth: usize,
+    start: Option<usize>,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl SegmentSplitter {
+    fn new() -> Self {
+        SegmentSplitter {
+            buf: String::new(),
+            depth: 0,
+            start: None,
+            in_string: false,
+            escaped: false,
+        }
+    }
+
+    /// Appends a chunk and returns every segment object that completed in it.
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut segments = Vec::new();
+        let text = String::from_utf8_lossy(chunk);
+        for ch in text.chars() {
+            let idx = self.buf.len();
+            self.buf.push(ch);
+            if self.in_string {
+                match ch {
+                    _ if self.escaped => self.escaped = false,
+                    '\\' => self.escaped = true,
+                    '"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match ch {
+                '"' => self.in_string = true,
+                '{' => {
+                    if self.depth == 0 {
+                        self.start = Some(idx);
+                    }
+                    self.depth += 1;
+                }
+                '}' => {
+                    // Guard against a stray/unbalanced `}` in a malformed or
+                    // truncated chunk so `depth` can't underflow and panic.
+                    if self.depth > 0 {
+                        self.depth -= 1;
+                    }
+                    if self.depth == 0 {
+                        if let Some(start) = self.start.take() {
+                            segments.push(self.buf[start..=idx].to_string());
+                        }
+                        // Drop everything buffered so far (array punctuation
+                        // and the finished segment) to keep memory bounded.
+                        self.buf.clear();
+                    }
+                }
+                _ => {}
+            }
+        }
+        segments
+    }
 }
 
 impl DruidClient {
+    /// Builds a client over the default reqwest transport. Requires the
+    /// `reqwest-backend` feature; non-reqwest runtimes use
+    /// [`DruidClient::with_transport`] instead.
+    #[cfg(feature = "reqwest-backend")]
     pub fn new(nodes: &Vec<String>) -> Self {
+        let retry_policy = RetryPolicy::default();
+        Self::with_transport(Self::default_transport(&retry_policy), nodes)
+    }
+
+    /// Builds a client over a caller-supplied transport, letting integrators
+    /// plug their own executor or an instrumented HTTP client while keeping
+    /// the static round-robin node pool.
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, nodes: &Vec<String>) -> Self {
+        let pool = nodes.iter().map(|node| Self::base_url(node)).collect();
         DruidClient {
-            http_client: Client::new(),
+            transport,
+            retry_policy: RetryPolicy::default(),
+            capture: None,
             nodes: nodes.clone(),
+            pool: Arc::new(RwLock::new(pool)),
+            cursor: Arc::new(AtomicUsize::new(0)),
+            _zk: None,
         }
     }
 
-    pub fn url(&self) -> &str {
-        "http://localhost:8888/druid/v2/?pretty"
+    /// Registers a callback that receives the raw request/response bodies of
+    /// every completed query.
+    pub fn with_capture(mut self, capture: CaptureHook) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Overrides the retry policy. The per-request timeout is handed to the
+    /// transport with each send rather than baked in by rebuilding it, so a
+    /// transport installed via [`DruidClient::with_transport`] is left
+    /// untouched and keeps working regardless of builder order.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    #[cfg(feature = "reqwest-backend")]
+    fn default_transport(retry_policy: &RetryPolicy) -> Arc<dyn HttpTransport> {
+        Arc::new(crate::transport::ReqwestTransport::with_timeout(
+            retry_policy.timeout,
+        ))
+    }
+
+    /// Connects to ZooKeeper and discovers live brokers under the default
+    /// Druid discovery path, keeping the pool up to date as brokers join and
+    /// leave the cluster. Uses the default reqwest transport.
+    #[cfg(feature = "reqwest-backend")]
+    pub fn from_zookeeper(zk_connect: &str) -> Result<Self, DruidClientError> {
+        Self::from_zookeeper_path(zk_connect, DEFAULT_BROKER_DISCOVERY_PATH)
+    }
+
+    /// Like [`DruidClient::from_zookeeper`] but lets the caller override the
+    /// discovery znode (useful for custom `druid.discovery.curator.path`).
+    #[cfg(feature = "reqwest-backend")]
+    pub fn from_zookeeper_path(
+        zk_connect: &str,
+        discovery_path: &str,
+    ) -> Result<Self, DruidClientError> {
+        let zk = ZooKeeper::connect(zk_connect, Duration::from_secs(15), |_| {})
+            .map_err(|source| DruidClientError::Zookeeper { source })?;
+        let zk = Arc::new(zk);
+
+        let pool = Arc::new(RwLock::new(Vec::new()));
+        let zk_slot = Arc::new(RwLock::new(Some(zk.clone())));
+        let watcher = BrokerWatcher {
+            zk: zk_slot,
+            path: discovery_path.to_string(),
+            pool: pool.clone(),
+        };
+        // Prime the pool and arm the initial watch.
+        refresh_pool(&zk, discovery_path, &pool, &watcher);
+
+        let retry_policy = RetryPolicy::default();
+        Ok(DruidClient {
+            transport: Self::default_transport(&retry_policy),
+            retry_policy,
+            capture: None,
+            nodes: vec![],
+            pool,
+            cursor: Arc::new(AtomicUsize::new(0)),
+            _zk: Some(zk),
+        })
+    }
+
+    /// Builds a Druid query base URL from a `host:port` node string.
+    fn base_url(node: &str) -> String {
+        format!("http://{}/druid/v2/", node.trim_end_matches('/'))
+    }
+
+    /// Picks the next broker base URL from the pool in round-robin order.
+    fn next_broker(&self) -> Result<String, DruidClientError> {
+        let pool = self.pool.read().unwrap();
+        if pool.is_empty() {
+            return Err(DruidClientError::NoBrokersAvailable);
+        }
+        let idx = self.cursor.fetch_add(1, AtomicOrdering::Relaxed) % pool.len();
+        Ok(pool[idx].clone())
     }
 
     pub async fn test_query(&self) -> Result<String, DruidClientError> {
-        let content = self
-            .http_client
-            .post(self.url())
-            .body(
-                r#"
+        let body = r#"
                 {
                     "queryType": "topN",
                     "dataSource": {
@@ -76,53 +531,239 @@ impl DruidClient {
                         }
                     ]
                 }
-            "#,
-            )
-            .header(reqwest::header::CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .map_err(|source| DruidClientError::HttpConnection { source: source })?
-            .text()
-            .await
-            .map_err(|source| DruidClientError::HttpConnection { source: source })?;
-
-        Ok(content)
+            "#;
+        let broker = self.next_broker()?;
+        self.send_once(&broker, body).await
     }
 
     async fn query_str(&self, query: &Query) -> Result<String, DruidClientError> {
         let request = serde_json::to_string(query)
-            .map_err(|err| DruidClientError::ParsingError { source: err });
+            .map_err(|err| DruidClientError::ParsingError { source: err })?;
 
-        let response = self
-            .http_client
-            .post(self.url())
-            .body(dbg!(request?.clone()))
-            .header(reqwest::header::CONTENT_TYPE, "application/json")
-            .send()
-            .await
-            .map_err(|source| DruidClientError::HttpConnection { source: source })?
-            .text()
+        // Try successive brokers on connection-level failures, backing off
+        // between attempts, until one answers or the policy is exhausted.
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let broker = self.next_broker()?;
+            let started = std::time::Instant::now();
+            // The per-request timeout is enforced inside the transport using
+            // its own runtime's timer, so this path stays runtime-agnostic.
+            match self.send_once(&broker, &request).await {
+                Ok(response) => {
+                    self.observe(&broker, &request, &response, started.elapsed());
+                    return Ok(response);
+                }
+                Err(err) if err.is_retryable() => {
+                    if attempts >= self.retry_policy.max_attempts {
+                        return Err(DruidClientError::AllBrokersFailed { attempts });
+                    }
+                    let _ = &err;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%broker, attempts, error = %err, "broker request failed, retrying");
+                    backend_sleep(self.retry_policy.backoff).await;
+                }
+                Err(other) => return Err(other),
+            }
+        }
+    }
+
+    /// Hands the raw request/response to the capture hook and emits a tracing
+    /// event recording the broker, the request body, the response size and
+    /// the elapsed time.
+    fn observe(&self, broker: &str, request: &str, response: &str, elapsed: Duration) {
+        if let Some(hook) = &self.capture {
+            hook(broker, request, response);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            %broker,
+            query = %request,
+            response_bytes = response.len(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            "druid query completed"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = elapsed;
+    }
+
+    /// Sends a single request to `broker` and returns the body as text,
+    /// handing the per-request timeout to the transport.
+    async fn send_once(&self, broker: &str, request: &str) -> Result<String, DruidClientError> {
+        let mut headers = std::collections::HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        self.transport
+            .post(
+                broker,
+                request.to_string(),
+                headers,
+                Some(self.retry_policy.timeout),
+            )
             .await
-            .map_err(|source| DruidClientError::HttpConnection { source: source })?;
-        Ok(response)
     }
 
     pub async fn query<'a, T: DeserializeOwned + std::fmt::Debug + Serialize>(
         &self,
         query: &Query,
-    ) -> Result<Vec<QueryResult<T>>, DruidClientError> {
-        let response_str = dbg!(self.query_str(query).await)?;
+    ) -> Result<QueryResponse<T>, DruidClientError> {
+        let response_str = self.query_str(query).await?;
         let json_value = serde_json::from_str::<serde_json::Value>(&response_str)
             .map_err(|err| DruidClientError::ParsingError { source: err });
-        if let Some(error) = json_value?.get("error") {
+        if json_value?.get("error").is_some() {
             return Err(DruidClientError::ServerError {
                 response: response_str,
             });
         }
-        let response = serde_json::from_str::<Vec<QueryResult<T>>>(&response_str)
-            .map_err(|source| DruidClientError::ParsingError { source: source });
+        // Each query type has its own envelope; decode the one that matches
+        // the submitted variant so the timestamp fields are preserved.
+        let response = match query {
+            Query::TopN { .. } => QueryResponse::TopN(Self::decode(&response_str)?),
+            Query::GroupBy { .. } => QueryResponse::GroupBy(Self::decode(&response_str)?),
+            Query::Scan { .. } => QueryResponse::Scan(Self::decode(&response_str)?),
+            // These return custom envelopes that don't fit QueryResponse<T>;
+            // point the caller at the dedicated typed method rather than
+            // misreporting a valid response as a server error.
+            Query::Search { .. } => {
+                return Err(DruidClientError::UseTypedMethod { query_type: "search" })
+            }
+            Query::TimeBoundary { .. } => {
+                return Err(DruidClientError::UseTypedMethod { query_type: "timeBoundary" })
+            }
+            Query::SegmentMetadata { .. } => {
+                return Err(DruidClientError::UseTypedMethod {
+                    query_type: "segmentMetadata",
+                })
+            }
+            Query::DataSourceMetadata { .. } => {
+                return Err(DruidClientError::UseTypedMethod {
+                    query_type: "dataSourceMetadata",
+                })
+            }
+        };
+        Ok(response)
+    }
 
-        response
+    /// Streams the rows of a `Scan` query without buffering the whole body.
+    ///
+    /// The reqwest response is kept as a byte stream; each top-level segment
+    /// envelope is decoded as soon as it arrives and its `events` are yielded
+    /// one at a time, keeping memory bounded regardless of interval width.
+    /// `batch_size` on the query acts as a backpressure hint for how many rows
+    /// Druid emits per chunk.
+    ///
+    /// Only `ResultFormat::List` (object rows) is decoded; `CompactedList`
+    /// emits rows as positional JSON arrays and is not supported here. Unlike
+    /// [`DruidClient::query`], streaming talks to a single broker and does not
+    /// go through the retry/failover loop or the capture hook.
+    ///
+    /// Incremental byte streaming relies on reqwest, so this method is only
+    /// available with the default `reqwest-backend` feature.
+    #[cfg(feature = "reqwest-backend")]
+    pub fn query_stream<'a, T: DeserializeOwned + std::fmt::Debug + Serialize + 'a>(
+        &'a self,
+        query: &'a Query,
+    ) -> impl Stream<Item = Result<T, DruidClientError>> + 'a {
+        async_stream::try_stream! {
+            // `batchSize` is Druid's per-segment row count; use it as the hint
+            // for how many rows to hold before handing them to the consumer, so
+            // a wide segment can't balloon the pending buffer.
+            let batch_size = match query {
+                Query::Scan { batch_size, .. } => *batch_size,
+                _ => 1,
+            };
+            let request = serde_json::to_string(query)
+                .map_err(|source| DruidClientError::ParsingError { source })?;
+            let broker = self.next_broker()?;
+            let client = Client::builder()
+                .timeout(self.retry_policy.timeout)
+                .build()
+                .unwrap_or_else(|_| Client::new());
+            let response = client
+                .post(&broker)
+                .body(request)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .send()
+                .await
+                .map_err(|source| DruidClientError::HttpConnection { source })?;
+
+            let mut bytes = response.bytes_stream();
+            let mut splitter = SegmentSplitter::new();
+            let mut pending: Vec<T> = Vec::with_capacity(batch_size);
+            while let Some(chunk) = bytes.next().await {
+                let chunk = chunk.map_err(|source| DruidClientError::HttpConnection { source })?;
+                for segment in splitter.push(&chunk) {
+                    let decoded = serde_json::from_str::<ScanResult<T>>(&segment)
+                        .map_err(|source| DruidClientError::ParsingError { source })?;
+                    for event in decoded.events {
+                        pending.push(event);
+                        // Drain a full batch at a time; the consumer pulling
+                        // each item is the natural backpressure signal.
+                        if pending.len() >= batch_size {
+                            for event in pending.drain(..) {
+                                yield event;
+                            }
+                        }
+                    }
+                }
+            }
+            for event in pending.drain(..) {
+                yield event;
+            }
+        }
+    }
+
+    /// Decodes a success response into the concrete envelope `R`.
+    fn decode<R: DeserializeOwned>(response_str: &str) -> Result<R, DruidClientError> {
+        serde_json::from_str::<R>(response_str)
+            .map_err(|source| DruidClientError::ParsingError { source })
+    }
+
+    /// Runs a `Search` query and decodes the per-bucket hit lists.
+    pub async fn search(&self, query: &Query) -> Result<Vec<SearchResult>, DruidClientError> {
+        self.query_typed(query).await
+    }
+
+    /// Runs a `TimeBoundary` query and decodes the min/max timestamps.
+    pub async fn time_boundary(
+        &self,
+        query: &Query,
+    ) -> Result<Vec<TimeBoundaryResult>, DruidClientError> {
+        self.query_typed(query).await
+    }
+
+    /// Runs a `SegmentMetadata` query and decodes the per-segment analysis.
+    pub async fn segment_metadata(
+        &self,
+        query: &Query,
+    ) -> Result<Vec<SegmentMetadataResult>, DruidClientError> {
+        self.query_typed(query).await
+    }
+
+    /// Runs a `DataSourceMetadata` query and decodes the last ingested event
+    /// time for the datasource.
+    pub async fn data_source_metadata(
+        &self,
+        query: &Query,
+    ) -> Result<Vec<DataSourceMetadataResult>, DruidClientError> {
+        self.query_typed(query).await
+    }
+
+    /// Sends `query` and decodes the raw response into `R`, surfacing any
+    /// Druid-side error before attempting to parse the success envelope.
+    async fn query_typed<R: DeserializeOwned>(
+        &self,
+        query: &Query,
+    ) -> Result<R, DruidClientError> {
+        let response_str = self.query_str(query).await?;
+        let json_value = serde_json::from_str::<serde_json::Value>(&response_str)
+            .map_err(|err| DruidClientError::ParsingError { source: err })?;
+        if json_value.get("error").is_some() {
+            return Err(DruidClientError::ServerError {
+                response: response_str,
+            });
+        }
+        serde_json::from_str::<R>(&response_str)
+            .map_err(|source| DruidClientError::ParsingError { source })
     }
 }
 
@@ -134,6 +775,7 @@ mod test {
         model::{HavingSpec, LimitSpec, PostAggregation, PostAggregator, ResultFormat},
         Filter, JoinType, Ordering, OutputType, SortingOrder,
     };
+    #[cfg(feature = "reqwest-backend")]
     #[test]
     fn test_basic() {
         let druid_client = DruidClient::new(&vec!["ololo".into()]);
@@ -148,6 +790,7 @@ mod test {
         count: usize,
     }
 
+    #[cfg(feature = "reqwest-backend")]
     #[test]
     fn test_top_n_query() {
         let top_n = Query::TopN {
@@ -171,6 +814,7 @@ mod test {
         println!("{:?}", result.unwrap());
     }
 
+    #[cfg(feature = "reqwest-backend")]
     #[test]
     fn test_scan_join() {
         let scan = Query::Scan {
@@ -209,6 +853,7 @@ mod test {
         let result = tokio_test::block_on(druid_client.query::<WikiPage>(&scan));
         println!("{:?}", result.unwrap());
     }
+    #[cfg(feature = "reqwest-backend")]
     #[test]
     fn test_group_by() {
         let group_by = Query::GroupBy {
@@ -254,4 +899,117 @@ mod test {
         let result = tokio_test::block_on(druid_client.query::<WikiPage>(&group_by));
         println!("{:?}", result.unwrap());
     }
+
+    #[test]
+    fn test_base_url_from_node() {
+        assert_eq!(
+            DruidClient::base_url("broker-1:8082"),
+            "http://broker-1:8082/druid/v2/"
+        );
+        // A trailing slash on the node must not produce a doubled slash.
+        assert_eq!(
+            DruidClient::base_url("broker-1:8082/"),
+            "http://broker-1:8082/druid/v2/"
+        );
+    }
+
+    #[cfg(feature = "reqwest-backend")]
+    #[test]
+    fn test_round_robin_cursor() {
+        let client = DruidClient::new(&vec!["h1:8082".into(), "h2:8082".into()]);
+        assert_eq!(client.next_broker().unwrap(), "http://h1:8082/druid/v2/");
+        assert_eq!(client.next_broker().unwrap(), "http://h2:8082/druid/v2/");
+        assert_eq!(client.next_broker().unwrap(), "http://h1:8082/druid/v2/");
+    }
+
+    #[cfg(feature = "reqwest-backend")]
+    #[test]
+    fn test_empty_pool_errors() {
+        let client = DruidClient::new(&vec![]);
+        assert!(matches!(
+            client.next_broker(),
+            Err(DruidClientError::NoBrokersAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_segment_splitter_carves_objects_across_chunks() {
+        let mut splitter = SegmentSplitter::new();
+        // A complete object, then a second one split across two pushes.
+        let mut out = splitter.push(br#"[{"segmentId":"a","columns":[],"events":[]},{"segment"#);
+        assert_eq!(out.len(), 1);
+        assert!(out[0].contains("\"segmentId\":\"a\""));
+        out = splitter.push(br#"Id":"b","columns":[],"events":[]}]"#);
+        assert_eq!(out.len(), 1);
+        assert!(out[0].contains("\"segmentId\":\"b\""));
+    }
+
+    #[test]
+    fn test_segment_splitter_ignores_braces_in_strings() {
+        let mut splitter = SegmentSplitter::new();
+        let out = splitter.push(br#"[{"segmentId":"}{","columns":[],"events":[]}]"#);
+        assert_eq!(out.len(), 1);
+        let decoded: ScanResult<WikiPage> = serde_json::from_str(&out[0]).unwrap();
+        assert_eq!(decoded.segment_id, "}{");
+    }
+
+    #[test]
+    fn test_topn_result_preserves_timestamp() {
+        let json = r#"[{"timestamp":"2021-01-01T00:00:00.000Z","result":[{"page":"Main","user":null,"count":5}]}]"#;
+        let parsed: Vec<TopNResult<WikiPage>> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed[0].timestamp, "2021-01-01T00:00:00.000Z");
+        assert_eq!(parsed[0].result[0].count, 5);
+    }
+
+    #[test]
+    fn test_group_by_result_decodes_event() {
+        let json = r#"[{"version":"v1","timestamp":"2021-01-01T00:00:00.000Z","event":{"page":"Main","user":"bob","count":2}}]"#;
+        let parsed: Vec<GroupByResult<WikiPage>> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed[0].version, "v1");
+        assert_eq!(parsed[0].event.user.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn test_scan_result_decodes_events() {
+        let json = r#"[{"segmentId":"seg-1","columns":["page","user","count"],"events":[{"page":"A","user":null,"count":1},{"page":"B","user":"x","count":2}]}]"#;
+        let parsed: Vec<ScanResult<WikiPage>> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed[0].segment_id, "seg-1");
+        assert_eq!(parsed[0].events.len(), 2);
+    }
+
+    #[test]
+    fn test_search_result_deser() {
+        let json = r#"[{"timestamp":"2021-01-01T00:00:00.000Z","result":[{"dimension":"page","value":"Main","count":3}]}]"#;
+        let parsed: Vec<SearchResult> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed[0].result[0].value, "Main");
+        assert_eq!(parsed[0].result[0].count, 3);
+    }
+
+    #[test]
+    fn test_time_boundary_result_deser() {
+        let json = r#"[{"timestamp":"2021-01-01T00:00:00.000Z","result":{"minTime":"2021-01-01T00:00:00.000Z","maxTime":"2021-01-02T00:00:00.000Z"}}]"#;
+        let parsed: Vec<TimeBoundaryResult> = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed[0].result.max_time.as_deref(), Some("2021-01-02T00:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_datasource_metadata_result_deser() {
+        let json = r#"[{"timestamp":"2021-01-01T00:00:00.000Z","result":{"maxIngestedEventTime":"2021-01-01T12:00:00.000Z"}}]"#;
+        let parsed: Vec<DataSourceMetadataResult> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed[0].result.max_ingested_event_time.as_deref(),
+            Some("2021-01-01T12:00:00.000Z")
+        );
+    }
+
+    #[test]
+    fn test_broker_announcement_base_url() {
+        let plaintext = r#"{"druidNode":{"host":"broker-1","plaintextPort":8082,"tlsPort":-1,"enableTlsPort":false}}"#;
+        let announcement: BrokerAnnouncement = serde_json::from_str(plaintext).unwrap();
+        assert_eq!(announcement.base_url(), "http://broker-1:8082/druid/v2/");
+
+        let tls = r#"{"druidNode":{"host":"broker-2","plaintextPort":-1,"tlsPort":8282,"enableTlsPort":true}}"#;
+        let announcement: BrokerAnnouncement = serde_json::from_str(tls).unwrap();
+        assert_eq!(announcement.base_url(), "https://broker-2:8282/druid/v2/");
+    }
 }
@@ -0,0 +1,182 @@
+pub mod model;
+
+use crate::client::DruidClientError;
+use model::Query;
+use serde::{Deserialize, Serialize};
+
+/// A query datasource: a plain table, a nested sub-query, or a join of two
+/// datasources.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DataSource {
+    Table {
+        name: String,
+    },
+    Query {
+        query: Box<Query>,
+    },
+    Join {
+        left: Box<DataSource>,
+        right: Box<DataSource>,
+        #[serde(rename = "rightPrefix")]
+        right_prefix: String,
+        condition: String,
+        #[serde(rename = "joinType")]
+        join_type: JoinType,
+    },
+}
+
+impl DataSource {
+    pub fn table(name: impl Into<String>) -> Self {
+        DataSource::Table { name: name.into() }
+    }
+
+    pub fn query(query: Query) -> Self {
+        DataSource::Query {
+            query: Box::new(query),
+        }
+    }
+
+    pub fn join(join_type: JoinType) -> JoinBuilder {
+        JoinBuilder::new(join_type)
+    }
+}
+
+/// Builder for the join [`DataSource`] variant.
+pub struct JoinBuilder {
+    join_type: JoinType,
+    left: Option<DataSource>,
+    right: Option<DataSource>,
+    right_prefix: Option<String>,
+    condition: Option<String>,
+}
+
+impl JoinBuilder {
+    fn new(join_type: JoinType) -> Self {
+        JoinBuilder {
+            join_type,
+            left: None,
+            right: None,
+            right_prefix: None,
+            condition: None,
+        }
+    }
+
+    pub fn left(mut self, left: DataSource) -> Self {
+        self.left = Some(left);
+        self
+    }
+
+    pub fn right(mut self, right: DataSource, right_prefix: impl Into<String>) -> Self {
+        self.right = Some(right);
+        self.right_prefix = Some(right_prefix.into());
+        self
+    }
+
+    pub fn condition(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    pub fn build(self) -> Result<DataSource, DruidClientError> {
+        let missing = |field: &str| DruidClientError::InvalidHeader {
+            expected: field.to_string(),
+            found: "none".to_string(),
+        };
+        Ok(DataSource::Join {
+            left: Box::new(self.left.ok_or_else(|| missing("left"))?),
+            right: Box::new(self.right.ok_or_else(|| missing("right"))?),
+            right_prefix: self.right_prefix.ok_or_else(|| missing("rightPrefix"))?,
+            condition: self.condition.ok_or_else(|| missing("condition"))?,
+            join_type: self.join_type,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum JoinType {
+    Inner,
+    Left,
+}
+
+/// A query dimension spec.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Dimension {
+    Default {
+        dimension: String,
+        #[serde(rename = "outputName")]
+        output_name: String,
+        #[serde(rename = "outputType")]
+        output_type: OutputType,
+    },
+}
+
+impl Dimension {
+    pub fn default(dimension: impl Into<String>) -> Self {
+        let dimension = dimension.into();
+        Dimension::Default {
+            output_name: dimension.clone(),
+            dimension,
+            output_type: OutputType::STRING,
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum OutputType {
+    STRING,
+    LONG,
+    FLOAT,
+    DOUBLE,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    All,
+    None,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// Sort direction shared by scan ordering and order-by column specs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Ordering {
+    None,
+    Ascending,
+    Descending,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum SortingOrder {
+    Lexicographic,
+    Alphanumeric,
+    Numeric,
+    Strlen,
+}
+
+/// Query filters. Only the variants the client needs are modelled.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Filter {
+    Selector { dimension: String, value: String },
+}
+
+impl Filter {
+    pub fn selector(dimension: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Selector {
+            dimension: dimension.into(),
+            value: value.into(),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use super::{DataSource, Dimension, Filter, Granularity, Ordering, SortingOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A Druid native query. Each variant serializes with its `queryType`
+/// discriminator and the fields Druid expects for that query type.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "queryType")]
+pub enum Query {
+    #[serde(rename = "topN", rename_all = "camelCase")]
+    TopN {
+        data_source: DataSource,
+        dimension: Dimension,
+        threshold: usize,
+        metric: String,
+        aggregations: Vec<Aggregation>,
+        intervals: Vec<String>,
+        granularity: Granularity,
+    },
+    #[serde(rename = "scan", rename_all = "camelCase")]
+    Scan {
+        data_source: DataSource,
+        batch_size: usize,
+        intervals: Vec<String>,
+        result_format: ResultFormat,
+        columns: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filter: Option<Filter>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ordering: Option<Ordering>,
+        context: HashMap<String, String>,
+    },
+    #[serde(rename = "groupBy", rename_all = "camelCase")]
+    GroupBy {
+        data_source: DataSource,
+        dimensions: Vec<Dimension>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit_spec: Option<LimitSpec>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        having: Option<HavingSpec>,
+        granularity: Granularity,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filter: Option<Filter>,
+        aggregations: Vec<Aggregation>,
+        post_aggregations: Vec<PostAggregation>,
+        intervals: Vec<String>,
+        #[serde(rename = "subtotalsSpec")]
+        subtotal_spec: Vec<Vec<String>>,
+        context: HashMap<String, String>,
+    },
+    #[serde(rename = "search", rename_all = "camelCase")]
+    Search {
+        data_source: DataSource,
+        search_dimensions: Vec<String>,
+        query: SearchQuerySpec,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sort: Option<SearchSort>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        limit: Option<usize>,
+        intervals: Vec<String>,
+        granularity: Granularity,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filter: Option<Filter>,
+        context: HashMap<String, String>,
+    },
+    #[serde(rename = "timeBoundary", rename_all = "camelCase")]
+    TimeBoundary {
+        data_source: DataSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bound: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filter: Option<Filter>,
+        context: HashMap<String, String>,
+    },
+    #[serde(rename = "segmentMetadata", rename_all = "camelCase")]
+    SegmentMetadata {
+        data_source: DataSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        to_include: Option<ToInclude>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        merge: Option<bool>,
+        analysis_types: Vec<String>,
+        context: HashMap<String, String>,
+    },
+    #[serde(rename = "dataSourceMetadata", rename_all = "camelCase")]
+    DataSourceMetadata {
+        data_source: DataSource,
+        context: HashMap<String, String>,
+    },
+}
+
+/// Match spec for a `Search` query.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchQuerySpec {
+    InsensitiveContains {
+        value: String,
+    },
+    Fragment {
+        values: Vec<String>,
+        #[serde(rename = "caseSensitive")]
+        case_sensitive: bool,
+    },
+    Regex {
+        pattern: String,
+    },
+}
+
+/// Sort order for `Search` hits.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchSort {
+    #[serde(rename = "type")]
+    pub sort_type: String,
+}
+
+/// Column selection for a `SegmentMetadata` query's `toInclude`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ToInclude {
+    All,
+    None,
+    List { columns: Vec<String> },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum ResultFormat {
+    List,
+    CompactedList,
+    ValueVector,
+}
+
+/// A metric aggregation. Only the variants the client needs are modelled.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Aggregation {
+    Count {
+        name: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    StringFirst {
+        name: String,
+        field_name: String,
+        max_string_bytes: usize,
+    },
+}
+
+impl Aggregation {
+    pub fn count(name: impl Into<String>) -> Self {
+        Aggregation::Count { name: name.into() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename = "default", rename_all = "camelCase")]
+pub struct LimitSpec {
+    pub limit: usize,
+    pub columns: Vec<OrderByColumnSpec>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderByColumnSpec {
+    pub dimension: String,
+    pub direction: Ordering,
+    pub dimension_order: SortingOrder,
+}
+
+impl OrderByColumnSpec {
+    pub fn new(
+        dimension: impl Into<String>,
+        direction: Ordering,
+        dimension_order: SortingOrder,
+    ) -> Self {
+        OrderByColumnSpec {
+            dimension: dimension.into(),
+            direction,
+            dimension_order,
+        }
+    }
+}
+
+/// A `having` clause for group-by queries.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HavingSpec {
+    GreaterThan { aggregation: String, value: f64 },
+}
+
+impl HavingSpec {
+    pub fn greater_than(aggregation: impl Into<String>, value: f64) -> Self {
+        HavingSpec::GreaterThan {
+            aggregation: aggregation.into(),
+            value,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PostAggregation {
+    #[serde(rename_all = "camelCase")]
+    Arithmetic {
+        name: String,
+        #[serde(rename = "fn")]
+        Fn: String,
+        fields: Vec<PostAggregator>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ordering: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PostAggregator {
+    #[serde(rename_all = "camelCase")]
+    FieldAccess { name: String, field_name: String },
+    Constant { name: String, value: f64 },
+}
+
+impl PostAggregator {
+    pub fn field_access(name: impl Into<String>, field_name: impl Into<String>) -> Self {
+        PostAggregator::FieldAccess {
+            name: name.into(),
+            field_name: field_name.into(),
+        }
+    }
+
+    pub fn constant(name: impl Into<String>, value: f64) -> Self {
+        PostAggregator::Constant {
+            name: name.into(),
+            value,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::client::DruidClientError;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Abstraction over the HTTP client used to talk to Druid brokers, so the
+/// query layer is not wired to a particular async runtime. The default
+/// implementation is backed by `reqwest`/tokio; an `async-std` implementation
+/// is available behind the `async-std-backend` feature.
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// POSTs `body` to `url` with the given headers and returns the response
+    /// body as text. Each backend enforces `timeout` (when `Some`) with its own
+    /// runtime's timer, so the query layer never depends on a specific reactor.
+    async fn post(
+        &self,
+        url: &str,
+        body: String,
+        headers: HashMap<String, String>,
+        timeout: Option<Duration>,
+    ) -> Result<String, DruidClientError>;
+}
+
+#[cfg(feature = "reqwest-backend")]
+pub use reqwest_backend::ReqwestTransport;
+
+#[cfg(feature = "reqwest-backend")]
+mod reqwest_backend {
+    use super::*;
+    use reqwest::Client;
+
+    /// Default transport backed by `reqwest`.
+    pub struct ReqwestTransport {
+        client: Client,
+    }
+
+    impl ReqwestTransport {
+        /// Builds a transport over a default reqwest client. The per-request
+        /// timeout is applied by [`HttpTransport::post`], not baked in here.
+        pub fn with_timeout(_timeout: Duration) -> Self {
+            ReqwestTransport {
+                client: Client::new(),
+            }
+        }
+
+        /// Wraps an already configured (possibly instrumented) client.
+        pub fn new(client: Client) -> Self {
+            ReqwestTransport { client }
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for ReqwestTransport {
+        async fn post(
+            &self,
+            url: &str,
+            body: String,
+            headers: HashMap<String, String>,
+            timeout: Option<Duration>,
+        ) -> Result<String, DruidClientError> {
+            let mut request = self.client.post(url).body(body);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            // reqwest owns its own timer, so this works without a tokio timer
+            // being started by the caller.
+            if let Some(timeout) = timeout {
+                request = request.timeout(timeout);
+            }
+            request
+                .send()
+                .await
+                .map_err(|source| DruidClientError::HttpConnection { source })?
+                .text()
+                .await
+                .map_err(|source| DruidClientError::HttpConnection { source })
+        }
+    }
+}
+
+#[cfg(feature = "async-std-backend")]
+pub use async_std_backend::AsyncStdTransport;
+
+#[cfg(feature = "async-std-backend")]
+mod async_std_backend {
+    use super::*;
+    use http_client::http_types::{Method, Request};
+    use http_client::{h1::H1Client, HttpClient};
+
+    /// Transport for `async-std` users, backed by `http-client`/`async-h1`.
+    pub struct AsyncStdTransport {
+        client: H1Client,
+    }
+
+    impl AsyncStdTransport {
+        pub fn new() -> Self {
+            AsyncStdTransport {
+                client: H1Client::new(),
+            }
+        }
+    }
+
+    impl Default for AsyncStdTransport {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for AsyncStdTransport {
+        async fn post(
+            &self,
+            url: &str,
+            body: String,
+            headers: HashMap<String, String>,
+            timeout: Option<Duration>,
+        ) -> Result<String, DruidClientError> {
+            let url = url
+                .parse()
+                .map_err(|e| DruidClientError::Transport { message: format!("{e}") })?;
+            let mut request = Request::new(Method::Post, url);
+            for (name, value) in headers {
+                request.insert_header(name.as_str(), value.as_str());
+            }
+            request.set_body(body);
+            let send = self.client.send(request);
+            // Use async-std's own timer so no tokio reactor is required.
+            let mut response = match timeout {
+                Some(timeout) => async_std::future::timeout(timeout, send)
+                    .await
+                    .map_err(|_| DruidClientError::Transport {
+                        message: "request timed out".to_string(),
+                    })?
+                    .map_err(|e| DruidClientError::Transport { message: format!("{e}") })?,
+                None => send
+                    .await
+                    .map_err(|e| DruidClientError::Transport { message: format!("{e}") })?,
+            };
+            response
+                .body_string()
+                .await
+                .map_err(|e| DruidClientError::Transport { message: format!("{e}") })
+        }
+    }
+}